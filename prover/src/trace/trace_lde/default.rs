@@ -0,0 +1,552 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{LagrangeKernelEvaluationFrame, TraceLde, TraceOodFrame};
+use crate::{ColMatrix, RowMatrix};
+use air::EvaluationFrame;
+use math::FieldElement;
+use utils::collections::Vec;
+
+// DEFAULT TRACE LOW DEGREE EXTENSION
+// ================================================================================================
+/// Default implementation of the [TraceLde] trait.
+///
+/// Keeps the main and auxiliary trace segment LDEs fully in memory as [RowMatrix] tables, and
+/// serves frame reads directly out of them. The original trace polynomials are kept alongside the
+/// LDEs (as [ColMatrix] tables) so that out-of-domain evaluation does not require interpolating
+/// back out of the extended domain.
+pub struct DefaultTraceLde<E: FieldElement> {
+    main_segment_lde: RowMatrix<E::BaseField>,
+    main_segment_polys: ColMatrix<E::BaseField>,
+    aux_segment_ldes: Vec<RowMatrix<E>>,
+    aux_segment_polys: Vec<ColMatrix<E>>,
+    blowup: usize,
+    // (aux segment index, column index within that segment)
+    lagrange_kernel_col: Option<(usize, usize)>,
+}
+
+impl<E: FieldElement> DefaultTraceLde<E> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Creates a new trace low-degree extension table from the provided main trace segment LDE
+    /// and its source polynomials.
+    pub fn new(
+        main_trace_lde: RowMatrix<E::BaseField>,
+        main_trace_polys: ColMatrix<E::BaseField>,
+        blowup: usize,
+    ) -> Self {
+        Self {
+            main_segment_lde: main_trace_lde,
+            main_segment_polys: main_trace_polys,
+            aux_segment_ldes: Vec::new(),
+            aux_segment_polys: Vec::new(),
+            blowup,
+            lagrange_kernel_col: None,
+        }
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Marks the column at `col_idx` within the most recently added auxiliary segment as the
+    /// Lagrange kernel column.
+    ///
+    /// # Panics
+    /// Panics if no auxiliary segment has been added yet.
+    pub fn set_lagrange_kernel_column_idx(&mut self, col_idx: usize) {
+        let segment_idx = self.aux_segment_ldes.len().checked_sub(1).expect(
+            "cannot mark a Lagrange kernel column before any auxiliary segment has been added",
+        );
+        self.lagrange_kernel_col = Some((segment_idx, col_idx));
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns `v = log2(trace_len)`, i.e. the number of variables of the verifier-chosen point
+    /// the Lagrange kernel column was built from.
+    ///
+    /// `self.trace_len()` returns the size of the full LDE domain (`trace_length * blowup()`), so
+    /// it must be divided by `blowup()` to recover the original trace length before taking the
+    /// log.
+    fn lagrange_kernel_num_variables(&self) -> usize {
+        (self.trace_len() / self.blowup()).ilog2() as usize
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Evaluates a polynomial with base-field coefficients at an extension-field point using Horner's
+/// method.
+fn evaluate_poly<E: FieldElement>(coeffs: &[E::BaseField], x: E) -> E {
+    coeffs
+        .iter()
+        .rev()
+        .fold(E::ZERO, |acc, &coeff| acc * x + E::from(coeff))
+}
+
+/// Evaluates a polynomial with extension-field coefficients at an extension-field point using
+/// Horner's method.
+fn evaluate_poly_ext<E: FieldElement>(coeffs: &[E], x: E) -> E {
+    coeffs
+        .iter()
+        .rev()
+        .fold(E::ZERO, |acc, &coeff| acc * x + coeff)
+}
+
+impl<E: FieldElement> TraceLde<E> for DefaultTraceLde<E> {
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds the provided auxiliary segment LDE, together with the trace polynomials it was
+    /// built from, to this trace LDE.
+    fn add_aux_segment(&mut self, aux_segment_lde: RowMatrix<E>, aux_segment_polys: ColMatrix<E>) {
+        assert_eq!(
+            self.main_segment_lde.num_rows(),
+            aux_segment_lde.num_rows(),
+            "number of rows in auxiliary segment must be of the same as in the main segment"
+        );
+        assert_eq!(
+            aux_segment_lde.num_cols(),
+            aux_segment_polys.num_cols(),
+            "number of columns in auxiliary segment LDE must be the same as in its source polynomials"
+        );
+        self.aux_segment_ldes.push(aux_segment_lde);
+        self.aux_segment_polys.push(aux_segment_polys);
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns number of columns in the main segment of the execution trace.
+    fn main_trace_width(&self) -> usize {
+        self.main_segment_lde.num_cols()
+    }
+
+    /// Returns number of columns in the auxiliary segments of the execution trace.
+    fn aux_trace_width(&self) -> usize {
+        self.aux_segment_ldes
+            .iter()
+            .fold(0, |s, m| s + m.num_cols())
+    }
+
+    /// Returns the number of rows in the trace LDE (the execution trace length times `blowup()`).
+    fn trace_len(&self) -> usize {
+        self.main_segment_lde.num_rows()
+    }
+
+    /// Returns blowup factor which was used to extend original execution trace into trace LDE.
+    fn blowup(&self) -> usize {
+        self.blowup
+    }
+
+    /// Reads current and next rows from the main trace segment into the specified frame.
+    fn read_main_trace_frame_into(
+        &self,
+        lde_step: usize,
+        frame: &mut EvaluationFrame<E::BaseField>,
+    ) {
+        // at the end of the trace, next state wraps around and we read the first step again
+        let next_lde_step = (lde_step + self.blowup()) % self.trace_len();
+
+        // copy main trace segment values into the frame
+        frame.set_data(
+            self.main_segment_lde.row(lde_step).into(),
+            self.main_segment_lde.row(next_lde_step).into(),
+        );
+    }
+
+    /// Reads current and next rows from the auxiliary trace segments into the specified frame.
+    ///
+    /// The resulting frame spans all auxiliary segments: columns are laid out contiguously in
+    /// segment order, with each segment contributing `RowMatrix::num_cols()` columns, so the
+    /// frame has `aux_trace_width()` columns in total.
+    fn read_aux_trace_frame_into(&self, lde_step: usize, frame: &mut EvaluationFrame<E>) {
+        // at the end of the trace, next state wraps around and we read the first step again
+        let next_lde_step = (lde_step + self.blowup()) % self.trace_len();
+
+        // copy auxiliary trace segment values into the frame, one segment at a time
+        let mut current_row = Vec::with_capacity(self.aux_trace_width());
+        let mut next_row = Vec::with_capacity(self.aux_trace_width());
+        for segment in self.aux_segment_ldes.iter() {
+            current_row.extend_from_slice(segment.row(lde_step));
+            next_row.extend_from_slice(segment.row(next_lde_step));
+        }
+
+        frame.set_data(current_row, next_row);
+    }
+
+    /// Returns a reference to [RowMatrix] representing the main trace segment.
+    fn get_main_segment(&self) -> &RowMatrix<E::BaseField> {
+        &self.main_segment_lde
+    }
+
+    /// Returns a reference to a [RowMatrix] representing an auxiliary trace segment at the
+    /// specified index.
+    fn get_aux_segment(&self, aux_segment_idx: usize) -> &RowMatrix<E> {
+        &self.aux_segment_ldes[aux_segment_idx]
+    }
+
+    /// Returns the number of auxiliary trace segments.
+    fn num_aux_segments(&self) -> usize {
+        self.aux_segment_ldes.len()
+    }
+
+    /// Returns the index of the auxiliary column holding the Lagrange kernel, if one was set via
+    /// [DefaultTraceLde::set_lagrange_kernel_column_idx].
+    ///
+    /// The index returned is relative to the combined auxiliary frame produced by
+    /// `read_aux_trace_frame_into` (i.e. it already accounts for the column offset contributed by
+    /// any auxiliary segments preceding the one the Lagrange kernel column lives in).
+    fn get_lagrange_kernel_column_idx(&self) -> Option<usize> {
+        self.lagrange_kernel_col.map(|(segment_idx, col_idx)| {
+            let preceding_cols: usize = self.aux_segment_ldes[..segment_idx]
+                .iter()
+                .map(|segment| segment.num_cols())
+                .sum();
+            preceding_cols + col_idx
+        })
+    }
+
+    /// Reads the rows of the Lagrange kernel auxiliary column needed to evaluate the GKR lookup
+    /// argument at `lde_step` into the specified frame.
+    ///
+    /// # Panics
+    /// Panics if this trace does not have a Lagrange kernel auxiliary column.
+    fn read_lagrange_kernel_frame_into(
+        &self,
+        lde_step: usize,
+        frame: &mut LagrangeKernelEvaluationFrame<E>,
+    ) {
+        let (segment_idx, col_idx) = self
+            .lagrange_kernel_col
+            .expect("this trace does not have a Lagrange kernel auxiliary column");
+        let segment = &self.aux_segment_ldes[segment_idx];
+        let v = self.lagrange_kernel_num_variables();
+
+        let mut rows = Vec::with_capacity(v + 1);
+        rows.push(segment.row(lde_step)[col_idx]);
+        for k in 0..v {
+            let offset = (lde_step + self.blowup() * (1 << k)) % self.trace_len();
+            rows.push(segment.row(offset)[col_idx]);
+        }
+
+        *frame.frame_mut() = rows;
+    }
+
+    /// Evaluates the main and auxiliary trace segment polynomials (and, if present, the Lagrange
+    /// kernel column polynomial) at the out-of-domain point `z` and its shift `z·g`.
+    fn read_ood_frame(&self, z: E, g: E) -> TraceOodFrame<E> {
+        let main_current: Vec<E> = (0..self.main_trace_width())
+            .map(|col| evaluate_poly(self.main_segment_polys.get_column(col), z))
+            .collect();
+        let main_next: Vec<E> = (0..self.main_trace_width())
+            .map(|col| evaluate_poly(self.main_segment_polys.get_column(col), z * g))
+            .collect();
+        let mut main_frame = EvaluationFrame::new(self.main_trace_width());
+        main_frame.set_data(main_current, main_next);
+
+        let aux_frame = if self.aux_trace_width() > 0 {
+            let mut current = Vec::with_capacity(self.aux_trace_width());
+            let mut next = Vec::with_capacity(self.aux_trace_width());
+            for segment in self.aux_segment_polys.iter() {
+                for col in 0..segment.num_cols() {
+                    current.push(evaluate_poly_ext(segment.get_column(col), z));
+                    next.push(evaluate_poly_ext(segment.get_column(col), z * g));
+                }
+            }
+            let mut frame = EvaluationFrame::new(self.aux_trace_width());
+            frame.set_data(current, next);
+            Some(frame)
+        } else {
+            None
+        };
+
+        // the Lagrange kernel column's OOD evaluations are gathered at z and at z·g^{2^k} for
+        // k = 0..v-1, mirroring the {x, g·x, g^2·x, …} row set used when reading its frame
+        // over the LDE domain
+        let lagrange_kernel_frame = self.lagrange_kernel_col.map(|(segment_idx, col_idx)| {
+            let poly = self.aux_segment_polys[segment_idx].get_column(col_idx);
+            let v = self.lagrange_kernel_num_variables();
+
+            let mut evals = Vec::with_capacity(v + 1);
+            evals.push(evaluate_poly_ext(poly, z));
+            let mut power = g;
+            for _ in 0..v {
+                evals.push(evaluate_poly_ext(poly, z * power));
+                power = power * power;
+            }
+
+            LagrangeKernelEvaluationFrame::new(evals)
+        });
+
+        TraceOodFrame::new(main_frame, aux_frame, lagrange_kernel_frame)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use math::fields::f128::BaseElement;
+
+    fn row_matrix(rows: Vec<Vec<BaseElement>>) -> RowMatrix<BaseElement> {
+        RowMatrix::new(rows)
+    }
+
+    fn col_matrix(cols: Vec<Vec<BaseElement>>) -> ColMatrix<BaseElement> {
+        ColMatrix::new(cols)
+    }
+
+    #[test]
+    fn lagrange_kernel_frame_reads_from_the_marked_segment() {
+        // trace_len = 4, blowup = 1 => v = log2(4) = 2, so the frame has 3 rows
+        let main_lde = row_matrix(vec![
+            vec![BaseElement::new(0)],
+            vec![BaseElement::new(1)],
+            vec![BaseElement::new(2)],
+            vec![BaseElement::new(3)],
+        ]);
+        let main_polys = col_matrix(vec![vec![BaseElement::new(0); 4]]);
+        let mut lde = DefaultTraceLde::new(main_lde, main_polys, 1);
+
+        // aux segment 0 does not contain the Lagrange kernel column: if the reader ever falls
+        // back to segment 0, this test will catch it
+        let aux0_lde = row_matrix(vec![
+            vec![BaseElement::new(100)],
+            vec![BaseElement::new(101)],
+            vec![BaseElement::new(102)],
+            vec![BaseElement::new(103)],
+        ]);
+        let aux0_polys = col_matrix(vec![vec![BaseElement::new(0); 4]]);
+        lde.add_aux_segment(aux0_lde, aux0_polys);
+
+        // aux segment 1 holds the Lagrange kernel column at local index 1
+        let aux1_lde = row_matrix(vec![
+            vec![BaseElement::new(10), BaseElement::new(20)],
+            vec![BaseElement::new(11), BaseElement::new(21)],
+            vec![BaseElement::new(12), BaseElement::new(22)],
+            vec![BaseElement::new(13), BaseElement::new(23)],
+        ]);
+        let aux1_polys = col_matrix(vec![
+            vec![BaseElement::new(0); 4],
+            vec![BaseElement::new(0); 4],
+        ]);
+        lde.add_aux_segment(aux1_lde, aux1_polys);
+
+        lde.set_lagrange_kernel_column_idx(1);
+
+        let mut frame = LagrangeKernelEvaluationFrame::new_empty();
+        lde.read_lagrange_kernel_frame_into(0, &mut frame);
+
+        // offsets for lde_step = 0: x -> 0, g·x -> 0 + 1*1 = 1, g^2·x -> 0 + 1*2 = 2
+        assert_eq!(
+            frame.frame(),
+            &[
+                BaseElement::new(20),
+                BaseElement::new(21),
+                BaseElement::new(22)
+            ]
+        );
+
+        // the global index accounts for the 1 column contributed by aux segment 0
+        assert_eq!(lde.get_lagrange_kernel_column_idx(), Some(2));
+    }
+
+    #[test]
+    fn lagrange_kernel_frame_uses_the_original_trace_length_not_the_lde_domain_size() {
+        // trace_length = 4, blowup = 2 => trace_len() (the LDE domain size) = 8, but
+        // v = log2(trace_length) = 2, so the frame must still have 3 rows, not 4
+        let main_lde = row_matrix((0..8).map(|i| vec![BaseElement::new(i)]).collect());
+        let main_polys = col_matrix(vec![vec![BaseElement::new(0); 4]]);
+        let mut lde = DefaultTraceLde::new(main_lde, main_polys, 2);
+
+        let aux_lde = row_matrix((0..8).map(|i| vec![BaseElement::new(100 + i)]).collect());
+        let aux_polys = col_matrix(vec![vec![BaseElement::new(0); 4]]);
+        lde.add_aux_segment(aux_lde, aux_polys);
+
+        lde.set_lagrange_kernel_column_idx(0);
+
+        let mut frame = LagrangeKernelEvaluationFrame::new_empty();
+        lde.read_lagrange_kernel_frame_into(0, &mut frame);
+
+        // offsets for lde_step = 0: x -> 0, g·x -> 0 + 2*1 = 2, g^2·x -> 0 + 2*2 = 4
+        assert_eq!(
+            frame.frame(),
+            &[
+                BaseElement::new(100),
+                BaseElement::new(102),
+                BaseElement::new(104)
+            ]
+        );
+    }
+
+    #[test]
+    fn read_aux_trace_frame_into_spans_all_segments() {
+        // trace_len = 2, blowup = 1
+        let main_lde = row_matrix(vec![vec![BaseElement::new(0)], vec![BaseElement::new(0)]]);
+        let main_polys = col_matrix(vec![vec![BaseElement::new(0); 2]]);
+        let mut lde = DefaultTraceLde::new(main_lde, main_polys, 1);
+
+        // aux segment 0: 2 columns
+        let aux0_lde = row_matrix(vec![
+            vec![BaseElement::new(1), BaseElement::new(2)],
+            vec![BaseElement::new(3), BaseElement::new(4)],
+        ]);
+        let aux0_polys = col_matrix(vec![
+            vec![BaseElement::new(0); 2],
+            vec![BaseElement::new(0); 2],
+        ]);
+        lde.add_aux_segment(aux0_lde, aux0_polys);
+
+        // aux segment 1: 1 column
+        let aux1_lde = row_matrix(vec![vec![BaseElement::new(5)], vec![BaseElement::new(6)]]);
+        let aux1_polys = col_matrix(vec![vec![BaseElement::new(0); 2]]);
+        lde.add_aux_segment(aux1_lde, aux1_polys);
+
+        let mut frame = EvaluationFrame::new(lde.aux_trace_width());
+        lde.read_aux_trace_frame_into(0, &mut frame);
+
+        // columns are laid out contiguously in segment order: segment 0's 2 columns, then
+        // segment 1's 1 column
+        assert_eq!(
+            frame.current(),
+            &[
+                BaseElement::new(1),
+                BaseElement::new(2),
+                BaseElement::new(5)
+            ]
+        );
+        assert_eq!(
+            frame.next(),
+            &[
+                BaseElement::new(3),
+                BaseElement::new(4),
+                BaseElement::new(6)
+            ]
+        );
+    }
+
+    #[test]
+    fn evaluate_poly_matches_direct_evaluation_by_hand() {
+        // p(x) = 1 + 2x + 3x^2
+        let coeffs = [
+            BaseElement::new(1),
+            BaseElement::new(2),
+            BaseElement::new(3),
+        ];
+        let x = BaseElement::new(5);
+        let expected = coeffs[0] + coeffs[1] * x + coeffs[2] * x * x;
+
+        assert_eq!(evaluate_poly(&coeffs, x), expected);
+        assert_eq!(evaluate_poly_ext(&coeffs, x), expected);
+    }
+
+    #[test]
+    fn read_ood_frame_evaluates_lagrange_kernel_poly_from_the_marked_segment() {
+        // p(x) = 7 + 7x + 7x^2 + 7x^3
+        let main_lde = row_matrix(vec![vec![BaseElement::new(0)]; 4]);
+        let main_polys = col_matrix(vec![vec![BaseElement::new(7); 4]]);
+        let mut lde = DefaultTraceLde::new(main_lde, main_polys, 1);
+
+        // aux segment 0: unrelated polynomial the kernel column must NOT be evaluated from
+        let aux0_lde = row_matrix(vec![vec![BaseElement::new(0)]; 4]);
+        let aux0_polys = col_matrix(vec![vec![BaseElement::new(0); 4]]);
+        lde.add_aux_segment(aux0_lde, aux0_polys);
+
+        // aux segment 1 holds the Lagrange kernel column's polynomial: p(x) = 1 + x
+        let aux1_lde = row_matrix(vec![vec![BaseElement::new(0)]; 4]);
+        let aux1_polys = col_matrix(vec![vec![BaseElement::new(1), BaseElement::new(1)]]);
+        lde.add_aux_segment(aux1_lde, aux1_polys);
+
+        lde.set_lagrange_kernel_column_idx(0);
+
+        let z = BaseElement::new(9);
+        let g = BaseElement::new(2);
+        let ood_frame = lde.read_ood_frame(z, g);
+
+        // main_frame: p(z) and p(z·g) for p(x) = 7 + 7x + 7x^2 + 7x^3
+        let main_frame = ood_frame.main_frame();
+        assert_eq!(
+            main_frame.current(),
+            &[BaseElement::new(7) * (BaseElement::ONE + z + z * z + z * z * z)]
+        );
+        let zg = z * g;
+        assert_eq!(
+            main_frame.next(),
+            &[BaseElement::new(7) * (BaseElement::ONE + zg + zg * zg + zg * zg * zg)]
+        );
+
+        // aux_frame: column 0 is aux segment 0's all-zero polynomial, column 1 is aux segment
+        // 1's p(x) = 1 + x
+        let aux_frame = ood_frame.aux_frame().expect("aux frame should be present");
+        assert_eq!(
+            aux_frame.current(),
+            &[BaseElement::new(0), BaseElement::new(1) + z]
+        );
+        assert_eq!(
+            aux_frame.next(),
+            &[BaseElement::new(0), BaseElement::new(1) + zg]
+        );
+
+        // the Lagrange kernel frame must have v + 1 = 3 entries (trace_length = 4, blowup = 1,
+        // so v = log2(4) = 2), evaluated at z and at z·g^{2^k} for k = 0..v-1, not just the
+        // first entry
+        let lagrange_frame = ood_frame
+            .lagrange_kernel_frame()
+            .expect("Lagrange kernel frame should be present");
+        assert_eq!(
+            lagrange_frame.frame(),
+            &[
+                BaseElement::new(1) + z,
+                BaseElement::new(1) + z * g,
+                BaseElement::new(1) + z * g * g,
+            ]
+        );
+    }
+
+    #[test]
+    fn read_frames_for_fragment_matches_individual_frame_reads_across_wraparound() {
+        // trace_len = 4, blowup = 1, so lde_step 3's "next" step wraps around to lde_step 0
+        let main_lde = row_matrix(vec![
+            vec![BaseElement::new(0)],
+            vec![BaseElement::new(1)],
+            vec![BaseElement::new(2)],
+            vec![BaseElement::new(3)],
+        ]);
+        let main_polys = col_matrix(vec![vec![BaseElement::new(0); 4]]);
+        let mut lde = DefaultTraceLde::new(main_lde, main_polys, 1);
+
+        let aux_lde = row_matrix(vec![
+            vec![BaseElement::new(10)],
+            vec![BaseElement::new(11)],
+            vec![BaseElement::new(12)],
+            vec![BaseElement::new(13)],
+        ]);
+        let aux_polys = col_matrix(vec![vec![BaseElement::new(0); 4]]);
+        lde.add_aux_segment(aux_lde, aux_polys);
+
+        let mut seen_steps = Vec::new();
+        lde.read_frames_for_fragment(0..4, |lde_step, main_frame, aux_frame| {
+            let mut expected_main = EvaluationFrame::new(lde.main_trace_width());
+            lde.read_main_trace_frame_into(lde_step, &mut expected_main);
+            let mut expected_aux = EvaluationFrame::new(lde.aux_trace_width());
+            lde.read_aux_trace_frame_into(lde_step, &mut expected_aux);
+
+            assert_eq!(main_frame.current(), expected_main.current());
+            assert_eq!(main_frame.next(), expected_main.next());
+            assert_eq!(aux_frame.current(), expected_aux.current());
+            assert_eq!(aux_frame.next(), expected_aux.next());
+
+            seen_steps.push(lde_step);
+        });
+
+        // the wraparound step (3 -> 0) is included and was exercised above
+        assert_eq!(seen_steps, vec![0, 1, 2, 3]);
+    }
+}
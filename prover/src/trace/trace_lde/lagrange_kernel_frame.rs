@@ -0,0 +1,53 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use math::FieldElement;
+use utils::collections::Vec;
+
+// LAGRANGE KERNEL EVALUATION FRAME
+// ================================================================================================
+/// Evaluation frame for the Lagrange kernel auxiliary column used by LogUp-GKR lookup arguments.
+///
+/// Unlike [EvaluationFrame](air::EvaluationFrame), whose row set is `{x, g·x}`, the GKR evaluation
+/// check for the Lagrange kernel column needs the column's value at `{x, g·x, g^2·x, g^4·x, …,
+/// g^{2^{v-1}}·x}`, where `v = log2(trace_len)` is the number of variables of the verifier-chosen
+/// point `r` the column was built from.
+pub struct LagrangeKernelEvaluationFrame<E: FieldElement> {
+    frame: Vec<E>,
+}
+
+impl<E: FieldElement> LagrangeKernelEvaluationFrame<E> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Creates a new Lagrange kernel evaluation frame from the provided row values.
+    pub fn new(frame: Vec<E>) -> Self {
+        Self { frame }
+    }
+
+    /// Creates a new empty Lagrange kernel evaluation frame, to be filled in via
+    /// [LagrangeKernelEvaluationFrame::frame_mut].
+    pub fn new_empty() -> Self {
+        Self { frame: Vec::new() }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the row values stored in this frame, ordered as `{x, g·x, g^2·x, …, g^{2^{v-1}}·x}`.
+    pub fn frame(&self) -> &[E] {
+        &self.frame
+    }
+
+    /// Returns a mutable reference to the row values stored in this frame.
+    pub fn frame_mut(&mut self) -> &mut Vec<E> {
+        &mut self.frame
+    }
+
+    /// Returns the number of rows in this frame (`v + 1`).
+    pub fn num_rows(&self) -> usize {
+        self.frame.len()
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{ColMatrix, RowMatrix};
+use air::EvaluationFrame;
+use core::ops::Range;
+use math::FieldElement;
+use utils::collections::Vec;
+
+mod default;
+pub use default::DefaultTraceLde;
+
+mod lagrange_kernel_frame;
+pub use lagrange_kernel_frame::LagrangeKernelEvaluationFrame;
+
+mod ood_frame;
+pub use ood_frame::TraceOodFrame;
+
+// TRACE LOW DEGREE EXTENSION TRAIT
+// ================================================================================================
+/// Defines low-degree extension (LDE) of an execution trace.
+///
+/// This trait decouples the constraint evaluator from any particular trace storage strategy: the
+/// default implementation ([DefaultTraceLde]) keeps the full main and auxiliary segment LDEs in
+/// memory, but other implementations (memory-mapped, GPU-resident, or distributed-shard traces)
+/// can be plugged in without forking the prover.
+pub trait TraceLde<E: FieldElement>: Sync {
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds the provided auxiliary segment LDE, together with the trace polynomials it was
+    /// built from, to this trace LDE.
+    fn add_aux_segment(&mut self, aux_segment_lde: RowMatrix<E>, aux_segment_polys: ColMatrix<E>);
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns number of columns in the main segment of the execution trace.
+    fn main_trace_width(&self) -> usize;
+
+    /// Returns number of columns in the auxiliary segments of the execution trace.
+    fn aux_trace_width(&self) -> usize;
+
+    /// Returns the number of rows in the trace LDE (the execution trace length times `blowup()`).
+    fn trace_len(&self) -> usize;
+
+    /// Returns blowup factor which was used to extend original execution trace into trace LDE.
+    fn blowup(&self) -> usize;
+
+    /// Reads current and next rows from the main trace segment into the specified frame.
+    fn read_main_trace_frame_into(
+        &self,
+        lde_step: usize,
+        frame: &mut EvaluationFrame<E::BaseField>,
+    );
+
+    /// Reads current and next rows from the auxiliary trace segment into the specified frame.
+    fn read_aux_trace_frame_into(&self, lde_step: usize, frame: &mut EvaluationFrame<E>);
+
+    /// Returns a reference to the [RowMatrix] representing the main trace segment.
+    fn get_main_segment(&self) -> &RowMatrix<E::BaseField>;
+
+    /// Returns a reference to a [RowMatrix] representing an auxiliary trace segment at the
+    /// specified index.
+    fn get_aux_segment(&self, aux_segment_idx: usize) -> &RowMatrix<E>;
+
+    /// Returns the number of auxiliary trace segments.
+    fn num_aux_segments(&self) -> usize;
+
+    /// Returns the index of the auxiliary column holding the Lagrange kernel, if one was built
+    /// for this trace (e.g. to support a LogUp-GKR lookup argument).
+    ///
+    /// The index is relative to the combined auxiliary frame produced by
+    /// `read_aux_trace_frame_into` — implementations that store auxiliary segments separately
+    /// must account for the column offset contributed by segments preceding the one the Lagrange
+    /// kernel column actually lives in.
+    fn get_lagrange_kernel_column_idx(&self) -> Option<usize>;
+
+    /// Reads the rows of the Lagrange kernel auxiliary column needed to evaluate the GKR lookup
+    /// argument at `lde_step` into the specified frame.
+    ///
+    /// The rows gathered are `{x, g·x, g^2·x, g^4·x, …, g^{2^{v-1}}·x}`, where `x` is the row at
+    /// `lde_step` and `v = log2(trace_len() / blowup())`, the log of the execution trace length.
+    ///
+    /// # Panics
+    /// Panics if this trace does not have a Lagrange kernel auxiliary column.
+    fn read_lagrange_kernel_frame_into(
+        &self,
+        lde_step: usize,
+        frame: &mut LagrangeKernelEvaluationFrame<E>,
+    );
+
+    /// Evaluates the main and auxiliary trace segment polynomials (and, if present, the Lagrange
+    /// kernel column polynomial) at the out-of-domain point `z` and its shift `z·g`, returning a
+    /// single typed [TraceOodFrame].
+    fn read_ood_frame(&self, z: E, g: E) -> TraceOodFrame<E>;
+
+    /// Reads main and auxiliary trace frames for every step in `fragment_steps`, invoking
+    /// `action` once per step.
+    ///
+    /// The wrap-around `next_lde_step` offset is computed from `blowup()` and `trace_len()` pulled
+    /// out of the loop once, rather than re-derived on every call the way a sequence of individual
+    /// `read_main_trace_frame_into` / `read_aux_trace_frame_into` calls would — for both the main
+    /// and the auxiliary frame, since the auxiliary gather is inlined here rather than delegated
+    /// to `read_aux_trace_frame_into` per row. Because the constraint-evaluation domain is many
+    /// times smaller than the full LDE domain, this lets the constraint evaluator split it into
+    /// fragments and process them across threads.
+    fn read_frames_for_fragment<F>(&self, fragment_steps: Range<usize>, mut action: F)
+    where
+        F: FnMut(usize, &EvaluationFrame<E::BaseField>, &EvaluationFrame<E>),
+    {
+        let blowup = self.blowup();
+        let trace_len = self.trace_len();
+        let num_aux_segments = self.num_aux_segments();
+        let aux_trace_width = self.aux_trace_width();
+
+        let mut main_frame = EvaluationFrame::new(self.main_trace_width());
+        let mut aux_frame = EvaluationFrame::new(aux_trace_width);
+
+        for lde_step in fragment_steps {
+            let next_lde_step = (lde_step + blowup) % trace_len;
+
+            main_frame.set_data(
+                self.get_main_segment().row(lde_step).into(),
+                self.get_main_segment().row(next_lde_step).into(),
+            );
+
+            if num_aux_segments > 0 {
+                let mut current_row = Vec::with_capacity(aux_trace_width);
+                let mut next_row = Vec::with_capacity(aux_trace_width);
+                for segment_idx in 0..num_aux_segments {
+                    let segment = self.get_aux_segment(segment_idx);
+                    current_row.extend_from_slice(segment.row(lde_step));
+                    next_row.extend_from_slice(segment.row(next_lde_step));
+                }
+                aux_frame.set_data(current_row, next_row);
+            }
+
+            action(lde_step, &main_frame, &aux_frame);
+        }
+    }
+}
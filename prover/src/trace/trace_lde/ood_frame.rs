@@ -0,0 +1,60 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::LagrangeKernelEvaluationFrame;
+use air::EvaluationFrame;
+use math::FieldElement;
+
+// TRACE OUT-OF-DOMAIN FRAME
+// ================================================================================================
+/// Trace evaluations at an out-of-domain point `z` and its shift `z·g`, as required by the
+/// DEEP/OOD step of the STARK protocol.
+///
+/// Mirrors the layout used by [TraceLde](super::TraceLde) when reading frames over the LDE
+/// domain: current/next-state evaluations for the main segment, current/next-state evaluations
+/// for the auxiliary segments (if any), and the multi-point OOD evaluations of the Lagrange
+/// kernel column (if one is present).
+pub struct TraceOodFrame<E: FieldElement> {
+    main_frame: EvaluationFrame<E>,
+    aux_frame: Option<EvaluationFrame<E>>,
+    lagrange_kernel_frame: Option<LagrangeKernelEvaluationFrame<E>>,
+}
+
+impl<E: FieldElement> TraceOodFrame<E> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Creates a new out-of-domain trace frame from the provided main, auxiliary, and Lagrange
+    /// kernel evaluations.
+    pub fn new(
+        main_frame: EvaluationFrame<E>,
+        aux_frame: Option<EvaluationFrame<E>>,
+        lagrange_kernel_frame: Option<LagrangeKernelEvaluationFrame<E>>,
+    ) -> Self {
+        Self {
+            main_frame,
+            aux_frame,
+            lagrange_kernel_frame,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the out-of-domain evaluations of the main trace segment polynomials.
+    pub fn main_frame(&self) -> &EvaluationFrame<E> {
+        &self.main_frame
+    }
+
+    /// Returns the out-of-domain evaluations of the auxiliary trace segment polynomials, if the
+    /// trace has any auxiliary segments.
+    pub fn aux_frame(&self) -> Option<&EvaluationFrame<E>> {
+        self.aux_frame.as_ref()
+    }
+
+    /// Returns the out-of-domain evaluations of the Lagrange kernel column, if the trace has one.
+    pub fn lagrange_kernel_frame(&self) -> Option<&LagrangeKernelEvaluationFrame<E>> {
+        self.lagrange_kernel_frame.as_ref()
+    }
+}